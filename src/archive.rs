@@ -0,0 +1,96 @@
+//! `--archive` support: list the members of a `.tar`, `.tar.gz`/`.tgz`, or
+//! `.zip` file instead of the archive file itself. Gated behind the
+//! `archive` cargo feature so the `tar`/`flate2`/`zip` dependencies are
+//! optional for users who never pass `--archive`.
+#![cfg(feature = "archive")]
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::entry::{ArchiveMetadata, Entry, FileType, MetadataSource};
+
+/// Returns `true` if `path`'s extension indicates a supported archive type
+pub fn is_supported_archive(path: &Path) -> bool {
+    let name = path.to_string_lossy().to_lowercase();
+    name.ends_with(".tar") || name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".zip")
+}
+
+/// Reads an archive's members and synthesizes an [`Entry`] for each, taking
+/// name/size/mode/mtime from the archive's own headers instead of the
+/// filesystem (archive members have no real `std::fs::Metadata`)
+pub fn read_archive_entries(path: &Path) -> io::Result<Vec<Entry>> {
+    if path.to_string_lossy().to_lowercase().ends_with(".zip") {
+        read_zip_entries(path)
+    } else {
+        read_tar_entries(path)
+    }
+}
+
+fn read_tar_entries(path: &Path) -> io::Result<Vec<Entry>> {
+    let file = File::open(path)?;
+    let name = path.to_string_lossy().to_lowercase();
+    let reader: Box<dyn Read> = if name.ends_with(".gz") || name.ends_with(".tgz") {
+        Box::new(flate2::read::GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+
+    tar::Archive::new(reader)
+        .entries()?
+        .map(|entry_result| {
+            let entry = entry_result?;
+            let header = entry.header();
+
+            let name = entry.path()?.to_string_lossy().trim_end_matches('/').to_owned().into();
+            let r#type = if header.entry_type().is_dir() { FileType::Dir } else { FileType::File };
+            let metadata = MetadataSource::Archive(ArchiveMetadata {
+                size: header.size().unwrap_or(0),
+                mode: header.mode().unwrap_or(0),
+                modified: header.mtime()
+                    .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                    .unwrap_or(SystemTime::UNIX_EPOCH),
+                links: 1,
+                uid: header.uid().unwrap_or(0) as u32,
+                gid: header.gid().unwrap_or(0) as u32,
+            });
+
+            Ok(Entry { name, r#type, metadata, git_status: None })
+        })
+        .collect()
+}
+
+fn read_zip_entries(path: &Path) -> io::Result<Vec<Entry>> {
+    let mut archive = zip::ZipArchive::new(File::open(path)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    (0..archive.len())
+        .map(|index| {
+            let zip_entry = archive.by_index(index)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            let name = zip_entry.name().trim_end_matches('/').to_owned().into();
+            let r#type = if zip_entry.is_dir() { FileType::Dir } else { FileType::File };
+            let metadata = MetadataSource::Archive(ArchiveMetadata {
+                size: zip_entry.size(),
+                mode: zip_entry.unix_mode().unwrap_or(0),
+                modified: zip_modified_time(&zip_entry),
+                links: 1,
+                uid: 0,
+                gid: 0,
+            });
+
+            Ok(Entry { name, r#type, metadata, git_status: None })
+        })
+        .collect()
+}
+
+/// Zip's `last_modified` has no timezone and only MS-DOS-era precision;
+/// `to_time` interprets it as UTC, and an invalid date falls back to the epoch
+fn zip_modified_time(zip_entry: &zip::read::ZipFile) -> SystemTime {
+    zip_entry.last_modified()
+        .to_time().ok()
+        .map(|odt| SystemTime::UNIX_EPOCH + Duration::from_secs(odt.unix_timestamp().max(0) as u64))
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
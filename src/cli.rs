@@ -45,10 +45,36 @@ pub struct Flags {
     #[arg(short = 't', long = "sort-mtime")]
     pub sort_by_modified_time: bool,
 
+    /// sort by natural/version order (`file2` before `file10`); this is also the default when no other sort flag is given
+    #[arg(short = 'v', long = "sort-version")]
+    pub sort_by_version: bool,
+
     /// list files separated by `, `
     #[arg(short = 'm', long)]
     pub stream_output: bool,
 
+    /// list one entry per line, instead of the default column grid
+    #[arg(short = '1', long = "oneline")]
+    pub oneline: bool,
+
+    /// list subdirectories recursively
+    #[arg(short = 'R', long = "recursive")]
+    pub recursive: bool,
+
+    /// append a character to each entry indicating its type (`/`=dir, `*`=executable, `@`=symlink, `|`=fifo, `=`=socket)
+    #[arg(short = 'F', long = "classify")]
+    pub classify: bool,
+
+    /// list the entries inside a `.tar`/`.tar.gz`/`.zip` archive, rather than the archive file itself
+    #[cfg(feature = "archive")]
+    #[arg(long = "archive")]
+    pub archive: bool,
+
+    /// show a per-file git status column in `-l` output
+    #[cfg(feature = "git")]
+    #[arg(long = "git")]
+    pub git: bool,
+
     /// path to list entries from
     #[arg()]
     pub path: Option<PathBuf>,
@@ -1,29 +1,59 @@
 use crate::entry::Entry;
 use crate::cli::Flags;
 
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
 use mime_guess::from_path;
 use mime_guess::mime::{APPLICATION, IMAGE, TEXT, VIDEO};
 use colored::{Color, Colorize};
+use terminal_size::{terminal_size, Width};
+
+/// Fallback width used when stdout isn't a TTY (e.g. piped output)
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+/// Spaces inserted between adjacent grid columns
+const COLUMN_PADDING: usize = 2;
 
 pub struct FormatSizes {
     pub file_len: usize,
     pub sym_len: usize,
 }
 
-pub fn print_entries(entries: Vec<Entry>, flags: Flags) -> io::Result<()> {
+/// Prints one or more directory groups, as produced by a `-R` traversal.
+/// Every group gets a `path:` header when `-R` is set, matching coreutils
+/// `ls -R` (which headers the top-level directory too); all but the first
+/// header is preceded by a blank line.
+pub fn print_entries(groups: Vec<(PathBuf, Vec<Entry>)>, flags: Flags) -> io::Result<()> {
+    for (index, (path, entries)) in groups.iter().enumerate() {
+        if flags.recursive {
+            if index != 0 {
+                println!();
+            }
+            println!("{}:", path.display());
+        }
+        print_group(entries, &flags)?;
+    }
+    Ok(())
+}
 
+fn print_group(entries: &[Entry], flags: &Flags) -> io::Result<()> {
     let mut stdout = io::stdout();
+
+    // Grid layout is the default; -l and -1 print one entry per row, -m a
+    // comma-separated stream
+    if !flags.long_listing && !flags.stream_output && !flags.oneline {
+        return print_grid(entries, flags, &mut stdout);
+    }
+
     if let Err(error) = entries.iter().enumerate().try_for_each(|(index, entry)| {
         // Comma separate
         if index != 0 && flags.stream_output {
             write!(stdout, ", ")?;
         }
         // Get relevant formatting string lengths
-        let sizes = calculate_format_sizes(&entries, &flags);
+        let sizes = calculate_format_sizes(entries, flags);
 
         // Print entries
-        let result = entry.print_entry( &mut stdout, &flags, &sizes);
+        let result = entry.print_entry( &mut stdout, flags, &sizes);
         if flags.stream_output {
             stdout.flush()?;
         } else {
@@ -43,16 +73,27 @@ impl Entry {
         // stream_output flag returns the files and directories as a comma separated list
         if flags.stream_output {
             write!(writer, "{}", self.get_name())?;
+            if let Some(suffix) = self.classify_suffix(flags) {
+                write!(writer, "{suffix}")?;
+            }
             return Ok(());
         }
 
         if flags.long_listing {
+            #[cfg(feature = "git")]
+            if flags.git {
+                write!(writer, "{} ", self.git_status.unwrap_or('-'))?;
+            }
             write!(writer, "{} ", self.get_permissions())?;
             write!(writer, "{} ", pad_str(self.get_links(),sizes.sym_len))?;
-            write!(writer, "{} ", self.get_owners())?;
+            // Not every platform has a uid/gid concept (e.g. Windows), so
+            // the owner/group column is simply omitted there
+            if let Some(owners) = self.get_owners() {
+                write!(writer, "{} ", owners)?;
+            }
             write!(writer, "{} ",
                 if flags.human {
-                    format!("{}", pad_str(bytes_to_human(self.get_size()), sizes.file_len))
+                    pad_str(bytes_to_human(self.get_size()), sizes.file_len)
                 } else {
                     format!("{} ", pad_str(self.get_size().to_string(), sizes.file_len))
                 })?;
@@ -69,16 +110,16 @@ impl Entry {
 
 
         if flags.show_size && !flags.long_listing {
-            write!(writer,"{}",
-                if flags.human {
-                    format!("{}\t", bytes_to_human(self.get_size()))
-                } else {
-                    format!("{}\t", self.get_size())
-                })?;
+            let size_str = if flags.human {
+                bytes_to_human(self.get_size())
+            } else {
+                self.get_size().to_string()
+            };
+            write!(writer, "{} ", pad_str(size_str, sizes.file_len))?;
         }
 
         // Entries are color coded based on file type
-        let color = match from_path(&self.get_name()).first_or_octet_stream().type_() {
+        let color = match from_path(self.get_name()).first_or_octet_stream().type_() {
             IMAGE => Color::Blue,
             TEXT => Color::Yellow,
             APPLICATION => Color::Green,
@@ -88,10 +129,104 @@ impl Entry {
 
         write!(writer, "{}", self.get_name().color(color))?;
 
+        if let Some(suffix) = self.classify_suffix(flags) {
+            write!(writer, "{suffix}")?;
+        }
+
         Ok(())
     }
 }
 
+/// Lays entries out column-major, packing as many columns as fit the
+/// terminal width, matching coreutils `ls`'s default grid
+fn print_grid(entries: &[Entry], flags: &Flags, writer: &mut impl Write) -> io::Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let sizes = calculate_format_sizes(entries, flags);
+    let widths: Vec<usize> = entries.iter().map(|entry| display_width(entry, flags, &sizes)).collect();
+    let num_cols = compute_column_count(&widths, terminal_width());
+    let num_rows = entries.len().div_ceil(num_cols);
+
+    let mut col_widths = vec![0; num_cols];
+    for (index, &width) in widths.iter().enumerate() {
+        let col = index / num_rows;
+        col_widths[col] = col_widths[col].max(width);
+    }
+    for row in 0..num_rows {
+        for (col, &col_width) in col_widths.iter().enumerate() {
+            let index = col * num_rows + row;
+            let Some(entry) = entries.get(index) else {
+                continue;
+            };
+            entry.print_entry(writer, flags, &sizes)?;
+            if (col + 1) * num_rows + row < entries.len() {
+                let pad = col_width - widths[index] + COLUMN_PADDING;
+                write!(writer, "{:pad$}", "", pad = pad)?;
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Display width of an entry's printed name, ignoring ANSI color codes
+/// (which add zero printable width) but counting the trailing `/` for dirs,
+/// any `-F`/`--classify` suffix, and the `-s` size prefix `print_entry`
+/// writes before non-directory names in grid mode
+fn display_width(entry: &Entry, flags: &Flags, sizes: &FormatSizes) -> usize {
+    let mut width = entry.get_name().chars().count();
+    if entry.is_folder() {
+        width += 1;
+    } else {
+        if entry.classify_suffix(flags).is_some() {
+            width += 1;
+        }
+        if flags.show_size {
+            // +1 for the single space `print_entry` writes between the
+            // padded size field and the name
+            width += sizes.file_len + 1;
+        }
+    }
+    width
+}
+
+/// Finds the widest column count that still fits within `term_width`,
+/// starting from an upper bound and narrowing down until it fits
+fn compute_column_count(widths: &[usize], term_width: usize) -> usize {
+    let min_width = widths.iter().copied().min().unwrap_or(1).max(1);
+    // The last column carries no trailing padding, hence `term_width + COLUMN_PADDING`
+    let upper_bound = ((term_width + COLUMN_PADDING) / (min_width + COLUMN_PADDING)).max(1).min(widths.len());
+
+    for cols in (1..=upper_bound).rev() {
+        let rows = widths.len().div_ceil(cols);
+        let total: usize = (0..cols)
+            .map(|col| {
+                (0..rows)
+                    .filter_map(|row| widths.get(col * rows + row))
+                    .copied()
+                    .max()
+                    .unwrap_or(0)
+                    + COLUMN_PADDING
+            })
+            .sum();
+        if total.saturating_sub(COLUMN_PADDING) <= term_width {
+            return cols;
+        }
+    }
+    1
+}
+
+/// Queries the terminal width, falling back to [`DEFAULT_TERMINAL_WIDTH`]
+/// when stdout isn't a TTY (e.g. output is piped or redirected)
+fn terminal_width() -> usize {
+    if !io::stdout().is_terminal() {
+        return DEFAULT_TERMINAL_WIDTH;
+    }
+    terminal_size().map_or(DEFAULT_TERMINAL_WIDTH, |(Width(width), _)| width as usize)
+}
+
 fn pad_str(src: String, width: usize) -> String {
     format!("{:width$}", src, width = width)
 }
@@ -140,3 +275,28 @@ fn calculate_format_sizes(entries: &[Entry], flags: &Flags) -> FormatSizes {
         sym_len,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_column_count_packs_as_many_columns_as_fit() {
+        // 5 names of width 4 plus 2-space padding all fit on a single row
+        // (5 columns) within an 80-column terminal
+        let widths = vec![4, 4, 4, 4, 4];
+        assert_eq!(compute_column_count(&widths, 80), 5);
+    }
+
+    #[test]
+    fn compute_column_count_narrows_to_fit_a_tight_terminal() {
+        let widths = vec![10, 10, 10, 10];
+        assert_eq!(compute_column_count(&widths, 25), 2);
+    }
+
+    #[test]
+    fn compute_column_count_falls_back_to_one_column() {
+        let widths = vec![100];
+        assert_eq!(compute_column_count(&widths, 20), 1);
+    }
+}
@@ -1,30 +1,59 @@
 
 use chrono::{Local, TimeZone};
-use libc::{S_IRGRP, S_IROTH, S_IRUSR, S_IWGRP, S_IWOTH, S_IWUSR, S_IXGRP, S_IXOTH, S_IXUSR};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::ffi::OsString;
 use std::fs::{self, metadata, Metadata};
 use std::io;
-use std::os::unix::fs::{MetadataExt, PermissionsExt};
-use std::path::Path;
+#[cfg(unix)]
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
-use users::{get_group_by_gid, get_user_by_uid};
 
 use crate::cli::Flags;
 
-/// Enum to represent directories or files
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg(unix)]
+mod unix;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(unix)]
+use self::unix as platform;
+#[cfg(windows)]
+use self::windows as platform;
+
+/// Enum to represent directories, regular files, or one of the special
+/// unix file types. Grouping for sort purposes (dirs first, everything
+/// else alongside `File`) is done via `is_dir`/`dir_group_cmp` rather than
+/// a derived `Ord`, since deriving it would sort `Symlink`/`Fifo`/`Socket`
+/// into their own bucket after `File` instead of alongside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileType {
     Dir,
     File,
+    Symlink,
+    Fifo,
+    Socket,
 }
 
 impl From<fs::FileType> for FileType {
     fn from(file_type: fs::FileType) -> Self {
         if file_type.is_dir() {
-            Self::Dir
-        } else {
-            Self::File
+            return Self::Dir;
+        }
+        if file_type.is_symlink() {
+            return Self::Symlink;
+        }
+        #[cfg(unix)]
+        {
+            if file_type.is_fifo() {
+                return Self::Fifo;
+            }
+            if file_type.is_socket() {
+                return Self::Socket;
+            }
         }
+        Self::File
     }
 }
 
@@ -38,26 +67,147 @@ impl FileType {
     }
 }
 
+/// Compares two file types for sort *grouping* only: dirs sort before
+/// everything else, and all non-dir types (`File`, `Symlink`, `Fifo`,
+/// `Socket`) compare equal to one another so they interleave by name/size/
+/// mtime instead of being bucketed by variant.
+fn dir_group_cmp(a: FileType, b: FileType) -> Ordering {
+    match (a.is_dir(), b.is_dir()) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => Ordering::Equal,
+    }
+}
+
+/// Metadata for an entry, abstracted over a real filesystem [`Metadata`]
+/// and a synthesized archive member header. Archive members (see the
+/// `archive` module) have no real `std::fs::Metadata` backing them, so
+/// `Entry`'s accessors read through this instead of a `Metadata` directly.
+pub enum MetadataSource {
+    Fs(Metadata),
+    Archive(ArchiveMetadata),
+}
+
+/// Size, mode, mtime, link count, and ownership taken from an archive
+/// member's header fields rather than the filesystem
+pub struct ArchiveMetadata {
+    pub size: u64,
+    pub mode: u32,
+    pub modified: SystemTime,
+    pub links: u64,
+    pub uid: u32,
+    pub gid: u32,
+}
+
+impl MetadataSource {
+    fn len(&self) -> u64 {
+        match self {
+            Self::Fs(meta) => meta.len(),
+            Self::Archive(meta) => meta.size,
+        }
+    }
+
+    fn nlink(&self) -> u64 {
+        match self {
+            Self::Fs(meta) => platform::nlink(meta),
+            Self::Archive(meta) => meta.links,
+        }
+    }
+
+    fn modified(&self) -> io::Result<SystemTime> {
+        match self {
+            Self::Fs(meta) => meta.modified(),
+            Self::Archive(meta) => Ok(meta.modified),
+        }
+    }
+
+    /// Owner/group display string, or `None` on platforms (Windows) that
+    /// have no uid/gid concept through `std`
+    fn owners(&self) -> Option<String> {
+        match self {
+            Self::Fs(meta) => platform::get_file_owner_and_group(meta),
+            // Archive members carry their own uid/gid from the header,
+            // independent of the host platform, so always resolve them
+            #[cfg(unix)]
+            Self::Archive(meta) => Some(unix::lookup_owner_and_group(meta.uid, meta.gid)),
+            #[cfg(windows)]
+            Self::Archive(meta) => Some(format!("{} {}", meta.uid, meta.gid)),
+        }
+    }
+
+    /// Permission display string: an `rwxrwxrwx` triplet on unix (real FS
+    /// or archive mode bits, which are unix semantics regardless of host)
+    /// or a file-attributes string on Windows
+    fn permissions(&self) -> String {
+        match self {
+            Self::Fs(meta) => platform::parse_permissions(meta),
+            Self::Archive(meta) => render_mode_bits(meta.mode),
+        }
+    }
+
+    /// `true` if any execute bit is set, on platforms where that's meaningful
+    fn is_executable(&self) -> bool {
+        match self {
+            #[cfg(unix)]
+            Self::Fs(meta) => {
+                use std::os::unix::fs::PermissionsExt;
+                meta.permissions().mode() & 0o111 != 0
+            }
+            #[cfg(windows)]
+            Self::Fs(_) => false,
+            Self::Archive(meta) => meta.mode & 0o111 != 0,
+        }
+    }
+}
+
+/// Renders raw unix mode bits as an `rwxrwxrwx` permission string. Used for
+/// archive members, whose mode bits carry unix semantics regardless of the
+/// host platform (unlike a real FS entry's permissions, see `platform`)
+fn render_mode_bits(mode: u32) -> String {
+    const BITS: [(u32, char); 9] = [
+        (0o400, 'r'), (0o200, 'w'), (0o100, 'x'),
+        (0o040, 'r'), (0o020, 'w'), (0o010, 'x'),
+        (0o004, 'r'), (0o002, 'w'), (0o001, 'x'),
+    ];
+    BITS.iter().map(|&(bit, ch)| if mode & bit != 0 { ch } else { '-' }).collect()
+}
+
 /// Represents a File or a Dir with all the metadata
 pub struct Entry {
     pub name: OsString,
     pub r#type: FileType,
-    pub metadata: Metadata,
+    pub metadata: MetadataSource,
+    /// `--git` status indicator for this entry, or `None` when the `git`
+    /// feature is disabled, `--git` wasn't passed, or this path isn't
+    /// inside a git work tree. A single-file path or an archive member is
+    /// never looked up and is always `None`.
+    pub git_status: Option<char>,
 }
 
 impl Entry {
+    /// Gets the entry's display name, lossily converted to UTF-8
+    pub fn get_name(&self) -> String {
+        self.name.to_string_lossy().into_owned()
+    }
+
+    /// Returns `true` if this entry is a directory
+    pub fn is_folder(&self) -> bool {
+        self.r#type.is_dir()
+    }
+
     /// Gets permissions of entry
     pub fn get_permissions(&self) -> String {
-        parse_permissions(self.metadata.permissions().mode())
+        self.metadata.permissions()
     }
     /// Gets num of links
     pub fn get_links(&self) -> String {
         format!("{}", self.metadata.nlink())
     }
 
-    /// Get owner and group of entry
-    pub fn get_owners(&self) -> String {
-        get_file_owner_and_group(&self.metadata)
+    /// Get owner and group of entry, or `None` on platforms with no
+    /// uid/gid concept (Windows)
+    pub fn get_owners(&self) -> Option<String> {
+        self.metadata.owners()
     }
 
     /// Get modified (local) time of entry
@@ -67,33 +217,101 @@ impl Entry {
             Err(e) => format!("Error: {}",e)
         }
     }
+
+    /// Gets size (in bytes) of entry
+    pub fn get_size(&self) -> u64 {
+        self.metadata.len()
+    }
+
+    /// Returns `true` if any execute bit is set in this entry's mode
+    pub fn is_executable(&self) -> bool {
+        self.metadata.is_executable()
+    }
+
+    /// Returns the `-F`/`--classify` suffix character for this entry, if
+    /// any. Additive with color coding: the suffix is written separately,
+    /// uncolored, after the name.
+    pub fn classify_suffix(&self, flags: &Flags) -> Option<char> {
+        if !flags.classify {
+            return None;
+        }
+        match self.r#type {
+            FileType::Dir => Some('/'),
+            FileType::Symlink => Some('@'),
+            FileType::Fifo => Some('|'),
+            FileType::Socket => Some('='),
+            FileType::File if self.is_executable() => Some('*'),
+            _ => None,
+        }
+    }
 }
 
-pub fn get_entries(dir_path: Option<&Path>, flags: &Flags) -> io::Result<Vec<Entry>> {
+/// Lists the entries for `dir_path` (or the current directory). With
+/// `flags.recursive`, also descends into every child directory, returning
+/// one `(path, entries)` group per directory visited, top-down, in the
+/// order `ls -R` would print them.
+pub fn get_entries(dir_path: Option<&Path>, flags: &Flags) -> io::Result<Vec<(PathBuf, Vec<Entry>)>> {
     // Convert `dir_path` to Path object
-    let path = dir_path.as_ref().map(Path::new);
+    let path = dir_path.unwrap_or_else(|| Path::new("."));
 
-    // Check if it's a directory
-    if let Some(path) = path {
-        if !path.exists() {
-            return Ok(Vec::new());
-        }
-        if !path.is_dir() {
-            return Ok(vec![Entry {
-                name: path.file_name().unwrap_or_default().to_os_string(),
-                r#type: FileType::File,
-                metadata: if let Some(meta) = metadata(path).ok() {
-                    meta
-                } else {
-                    eprintln!("ERROR: Could not retrieve metadata!");
-                    std::process::exit(1)
-                },
-            }]);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    if !path.is_dir() {
+        #[cfg(feature = "archive")]
+        if flags.archive && crate::archive::is_supported_archive(path) {
+            let entries = crate::archive::read_archive_entries(path)?;
+            return Ok(vec![(path.to_path_buf(), entries)]);
         }
+
+        let entry = Entry {
+            name: path.file_name().unwrap_or_default().to_os_string(),
+            r#type: FileType::File,
+            metadata: if let Ok(meta) = metadata(path) {
+                MetadataSource::Fs(meta)
+            } else {
+                eprintln!("ERROR: Could not retrieve metadata!");
+                std::process::exit(1)
+            },
+            git_status: None,
+        };
+        return Ok(vec![(path.to_path_buf(), vec![entry])]);
     }
 
+    // Computed once for the whole listing (repo discovery and status
+    // computation are both too expensive to redo per entry), then
+    // consulted for every entry in every directory group below, including
+    // ones visited by `-R` recursion.
+    #[cfg(feature = "git")]
+    let git_statuses = flags.git.then(|| crate::git::collect_statuses(path)).flatten();
+    #[cfg(not(feature = "git"))]
+    let git_statuses = None;
+
+    let mut groups = Vec::new();
+    collect_dir_entries(path, flags, git_statuses.as_ref(), &mut groups)?;
+    Ok(groups)
+}
+
+/// Reads one directory's entries and, when recursing, appends a group for
+/// every child directory in turn. `fs::DirEntry::file_type` never follows
+/// symlinks, so a symlink to a directory is classified as `FileType::Symlink`
+/// rather than `FileType::Dir` and is never recursed into, which keeps this
+/// immune to symlink loops.
+fn collect_dir_entries(
+    path: &Path,
+    flags: &Flags,
+    git_statuses: Option<&HashMap<PathBuf, char>>,
+    groups: &mut Vec<(PathBuf, Vec<Entry>)>,
+) -> io::Result<()> {
+    // Resolved once per directory (not per entry) so a symlink entry is
+    // looked up under its own name rather than canonicalize()'s following it
+    // to its target; this also keeps the key comparable to the absolute
+    // `workdir`-joined paths `git::collect_statuses` inserts.
+    let dir_abs = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
     // Collect entries into vector, ignoring hidden entries if show_hidden is false
-    let mut entries: Vec<_> = fs::read_dir(path.unwrap_or_else(|| Path::new(".")))?
+    let mut entries: Vec<_> = fs::read_dir(path)?
         .filter_map(|entry_result| {
             let entry = match entry_result {
                 Ok(entry) => entry,
@@ -104,7 +322,7 @@ pub fn get_entries(dir_path: Option<&Path>, flags: &Flags) -> io::Result<Vec<Ent
             };
 
             let name = entry.file_name();
-            if !flags.show_hidden && is_hidden_folder(&entry.path()) {
+            if !flags.show_hidden && platform::is_hidden_folder(&entry.path()) {
                 return None;
             }
 
@@ -117,24 +335,51 @@ pub fn get_entries(dir_path: Option<&Path>, flags: &Flags) -> io::Result<Vec<Ent
             };
 
             let metadata = match entry.metadata() {
-                Ok(meta) => meta,
+                Ok(meta) => MetadataSource::Fs(meta),
                 Err(e) => {
                     eprintln!("Warning: Could not retrieve metadata: {}", e);
                     return None;
                 }
             };
 
+            let git_status = git_statuses.map(|statuses| {
+                statuses.get(&dir_abs.join(&name)).copied().unwrap_or('-')
+            });
+
             Some(Entry {
                 name,
                 r#type: file_type,
                 metadata,
+                git_status,
             })
         })
         .collect();
 
+    sort_entries(&mut entries, flags);
+
+    let subdirs: Vec<PathBuf> = if flags.recursive {
+        entries.iter()
+            .filter(|entry| entry.r#type.is_dir())
+            .map(|entry| path.join(&entry.name))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    groups.push((path.to_path_buf(), entries));
+
+    for subdir in subdirs {
+        collect_dir_entries(&subdir, flags, git_statuses, groups)?;
+    }
+
+    Ok(())
+}
 
-    // Sort the entries by relevant flag (by size or by time modified)
-    if flags.sort_by_size || flags.sort_by_modified_time {
+// Sorts the entries by relevant flag (by size or by time modified), falling
+// back to natural/version name order otherwise, since that's also the
+// default when no other sort flag is given
+fn sort_entries(entries: &mut [Entry], flags: &Flags) {
+    if (flags.sort_by_size || flags.sort_by_modified_time) && !flags.sort_by_version {
         entries.sort_unstable_by(|a, b| {
             let key = |entry: &Entry| {
                 let metadata = &entry.metadata;
@@ -158,58 +403,69 @@ pub fn get_entries(dir_path: Option<&Path>, flags: &Flags) -> io::Result<Vec<Ent
             if flags.reverse_sort {
                 ordering = ordering.reverse();
             }
-            Ord::cmp(&a.r#type, &b.r#type).then(ordering)
+            dir_group_cmp(a.r#type, b.r#type).then(ordering)
+        });
+    } else {
+        entries.sort_unstable_by(|a, b| {
+            let mut ordering = natural_cmp(&a.name, &b.name);
+            if flags.reverse_sort {
+                ordering = ordering.reverse();
+            }
+            dir_group_cmp(a.r#type, b.r#type).then(ordering)
         });
     }
-    Ok(entries)
-}
-
-// Checks if given Path is 'hidden' (starts with '.')
-fn is_hidden_folder(path: &Path) -> bool {
-    path.file_name()
-        .is_some_and(|name| name.as_encoded_bytes()[0] == b'.')
 }
 
-/// Helper functions to get and parse permissions of entries
-/// Credit to Matthias Endler at endler.dev
-fn parse_permissions(mode: u32) -> String {
-    let user = triplet(mode, S_IRUSR, S_IWUSR, S_IXUSR);
-    let group = triplet(mode, S_IRGRP, S_IWGRP, S_IXGRP);
-    let other = triplet(mode, S_IROTH, S_IWOTH, S_IXOTH);
-    [user, group, other].join("")
-}
+/// Locale-insensitive natural/version comparison: runs of digits are
+/// compared numerically (so `file2` sorts before `file10`) rather than
+/// character by character, everything else compares character by
+/// character, and any remaining tie falls back to plain byte comparison.
+fn natural_cmp(a: &OsString, b: &OsString) -> Ordering {
+    let a = a.to_string_lossy();
+    let b = b.to_string_lossy();
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
 
-fn triplet(mode: u32, read: u32, write: u32, execute: u32) -> String {
-    match (mode & read, mode & write, mode & execute) {
-        (0, 0, 0) => "---",
-        (_, 0, 0) => "r--",
-        (0, _, 0) => "-w-",
-        (0, 0, _) => "--x",
-        (_, 0, _) => "r-x",
-        (_, _, 0) => "rw-",
-        (0, _, _) => "-wx",
-        (_, _, _) => "rwx",
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (Some(ac), Some(bc)) if ac.is_ascii_digit() && bc.is_ascii_digit() => {
+                let a_digits = take_digit_run(&mut a_chars);
+                let b_digits = take_digit_run(&mut b_chars);
+                let a_trimmed = a_digits.trim_start_matches('0');
+                let b_trimmed = b_digits.trim_start_matches('0');
+                let ordering = a_trimmed.len().cmp(&b_trimmed.len()).then_with(|| a_trimmed.cmp(b_trimmed));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(ac), Some(bc)) => {
+                if ac != bc {
+                    return ac.cmp(&bc);
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (None, None) => break,
+        }
     }
-    .to_string()
-}
-
-
-/// Gets the owner and group names associated with a file
-pub fn get_file_owner_and_group(meta: &Metadata) -> String {
-    // Get owner and group IDs
-    let uid = meta.uid();
-    let gid = meta.gid();
 
-    // Look up the user and group names
-    let owner_name = get_user_by_uid(uid)
-        .map(|user| user.name().to_string_lossy().into_owned())
-        .unwrap_or_else(|| uid.to_string());
-
-    let group_name = get_group_by_gid(gid)
-        .map(|group| group.name().to_string_lossy().into_owned())
-        .unwrap_or_else(|| gid.to_string());
+    a.as_bytes().cmp(b.as_bytes())
+}
 
-    format!("{} {}", owner_name, group_name)
+/// Consumes and returns the maximal run of ASCII digits at the front of
+/// `chars`
+fn take_digit_run(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(&c) = chars.peek() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+        digits.push(c);
+        chars.next();
+    }
+    digits
 }
 
 /// Converts SystemTime of file metadata to readable string EX: Sep 10 14:23
@@ -225,3 +481,43 @@ fn get_file_date(modified_time: SystemTime) -> String {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(s: &str) -> OsString {
+        OsString::from(s)
+    }
+
+    #[test]
+    fn natural_cmp_orders_digit_runs_numerically() {
+        assert_eq!(natural_cmp(&name("file2"), &name("file10")), Ordering::Less);
+        assert_eq!(natural_cmp(&name("file10"), &name("file2")), Ordering::Greater);
+    }
+
+    #[test]
+    fn natural_cmp_ignores_leading_zeros_but_breaks_ties_on_bytes() {
+        // "01" and "1" are numerically equal, so the comparison falls through
+        // to a plain byte comparison, where `0` sorts before `1`
+        assert_eq!(natural_cmp(&name("file01"), &name("file1")), Ordering::Less);
+        assert_eq!(natural_cmp(&name("file1"), &name("file1")), Ordering::Equal);
+    }
+
+    #[test]
+    fn natural_cmp_falls_back_to_char_comparison_for_non_digits() {
+        assert_eq!(natural_cmp(&name("abc"), &name("abd")), Ordering::Less);
+    }
+
+    #[test]
+    fn dir_group_cmp_sorts_dirs_before_everything_else() {
+        assert_eq!(dir_group_cmp(FileType::Dir, FileType::File), Ordering::Less);
+        assert_eq!(dir_group_cmp(FileType::File, FileType::Dir), Ordering::Greater);
+    }
+
+    #[test]
+    fn dir_group_cmp_treats_all_non_dir_types_as_equal() {
+        assert_eq!(dir_group_cmp(FileType::File, FileType::Symlink), Ordering::Equal);
+        assert_eq!(dir_group_cmp(FileType::Fifo, FileType::Socket), Ordering::Equal);
+    }
+}
+
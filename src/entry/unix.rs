@@ -0,0 +1,64 @@
+//! Unix-specific metadata: rwx permission rendering, owner/group lookup,
+//! hard-link counts, and dotfile hidden detection.
+
+use libc::{S_IRGRP, S_IROTH, S_IRUSR, S_IWGRP, S_IWOTH, S_IWUSR, S_IXGRP, S_IXOTH, S_IXUSR};
+use std::fs::Metadata;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+use users::{get_group_by_gid, get_user_by_uid};
+
+/// Helper functions to get and parse permissions of entries
+/// Credit to Matthias Endler at endler.dev
+pub fn parse_permissions(meta: &Metadata) -> String {
+    let mode = meta.permissions().mode();
+    let user = triplet(mode, S_IRUSR, S_IWUSR, S_IXUSR);
+    let group = triplet(mode, S_IRGRP, S_IWGRP, S_IXGRP);
+    let other = triplet(mode, S_IROTH, S_IWOTH, S_IXOTH);
+    [user, group, other].join("")
+}
+
+fn triplet(mode: u32, read: u32, write: u32, execute: u32) -> String {
+    match (mode & read, mode & write, mode & execute) {
+        (0, 0, 0) => "---",
+        (_, 0, 0) => "r--",
+        (0, _, 0) => "-w-",
+        (0, 0, _) => "--x",
+        (_, 0, _) => "r-x",
+        (_, _, 0) => "rw-",
+        (0, _, _) => "-wx",
+        (_, _, _) => "rwx",
+    }
+    .to_string()
+}
+
+/// Number of hard links to the file
+pub fn nlink(meta: &Metadata) -> u64 {
+    meta.nlink()
+}
+
+/// Gets the owner and group names associated with a file
+pub fn get_file_owner_and_group(meta: &Metadata) -> Option<String> {
+    Some(lookup_owner_and_group(meta.uid(), meta.gid()))
+}
+
+/// Looks up the user/group names for a uid/gid pair, falling back to the
+/// numeric id when there's no matching passwd/group entry. Also used for
+/// archive members, whose uid/gid came from the archive header rather than
+/// a `Metadata`.
+pub fn lookup_owner_and_group(uid: u32, gid: u32) -> String {
+    let owner_name = get_user_by_uid(uid)
+        .map(|user| user.name().to_string_lossy().into_owned())
+        .unwrap_or_else(|| uid.to_string());
+
+    let group_name = get_group_by_gid(gid)
+        .map(|group| group.name().to_string_lossy().into_owned())
+        .unwrap_or_else(|| gid.to_string());
+
+    format!("{} {}", owner_name, group_name)
+}
+
+// Checks if given Path is 'hidden' (starts with '.')
+pub fn is_hidden_folder(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.as_encoded_bytes()[0] == b'.')
+}
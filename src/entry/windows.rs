@@ -0,0 +1,46 @@
+//! Windows equivalents of the unix-specific metadata in the `unix` module.
+//! Windows has no rwx bits, no uid/gid, and no portable hard-link count
+//! through `std`, so these read from file attributes instead and skip
+//! owner/group entirely (see `display.rs`, which tolerates the missing
+//! owner/group column).
+
+use std::fs::Metadata;
+use std::os::windows::fs::MetadataExt;
+use std::path::Path;
+
+const FILE_ATTRIBUTE_READONLY: u32 = 0x1;
+const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+const FILE_ATTRIBUTE_ARCHIVE: u32 = 0x20;
+
+/// Renders the file-attribute flags ls-style, since Windows has no rwx bits
+pub fn parse_permissions(meta: &Metadata) -> String {
+    let attrs = meta.file_attributes();
+    let flag = |bit, ch| if attrs & bit != 0 { ch } else { '-' };
+    [
+        flag(FILE_ATTRIBUTE_READONLY, 'r'),
+        flag(FILE_ATTRIBUTE_HIDDEN, 'h'),
+        flag(FILE_ATTRIBUTE_SYSTEM, 's'),
+        flag(FILE_ATTRIBUTE_ARCHIVE, 'a'),
+    ]
+    .into_iter()
+    .collect()
+}
+
+/// Windows doesn't expose a portable hard-link count through `std`
+pub fn nlink(_meta: &Metadata) -> u64 {
+    1
+}
+
+/// Windows has no uid/gid concept through `std`; the owner/group column is
+/// skipped entirely
+pub fn get_file_owner_and_group(_meta: &Metadata) -> Option<String> {
+    None
+}
+
+/// Hidden on Windows is an explicit file attribute, not a leading dot
+pub fn is_hidden_folder(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .map(|meta| meta.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+        .unwrap_or(false)
+}
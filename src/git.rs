@@ -0,0 +1,53 @@
+//! `--git` support: a per-file status column in `-l` output, akin to exa's
+//! git feature. Gated behind the `git` cargo feature so the `git2`
+//! dependency is optional for users who never pass `--git`.
+#![cfg(feature = "git")]
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use git2::{Repository, Status};
+
+/// Discovers the repo containing `dir_path` and computes every path's
+/// status in a single pass, mapping it to a one-character indicator: `M`
+/// modified, `A` added, `?` untracked, `I` ignored, `-` clean. Computed
+/// once per listing (see `entry::get_entries`) rather than per-entry,
+/// since repo discovery and status computation are both expensive.
+/// Returns `None` if `dir_path` isn't inside a git work tree.
+pub fn collect_statuses(dir_path: &Path) -> Option<HashMap<PathBuf, char>> {
+    let repo = Repository::discover(dir_path).ok()?;
+    let workdir = repo.workdir()?.to_path_buf();
+
+    let mut statuses = HashMap::new();
+    for status_entry in repo.statuses(None).ok()?.iter() {
+        let Some(path) = status_entry.path() else {
+            continue;
+        };
+        statuses.insert(workdir.join(path), status_char(status_entry.status()));
+    }
+
+    Some(statuses)
+}
+
+fn status_char(status: Status) -> char {
+    if status.is_ignored() {
+        'I'
+    } else if status.is_wt_new() {
+        '?'
+    } else if status.is_index_new() {
+        'A'
+    } else if status.intersects(
+        Status::WT_MODIFIED
+            | Status::WT_DELETED
+            | Status::WT_RENAMED
+            | Status::WT_TYPECHANGE
+            | Status::INDEX_MODIFIED
+            | Status::INDEX_DELETED
+            | Status::INDEX_RENAMED
+            | Status::INDEX_TYPECHANGE,
+    ) {
+        'M'
+    } else {
+        '-'
+    }
+}
@@ -0,0 +1,8 @@
+pub mod cli;
+pub mod display;
+pub mod entry;
+
+#[cfg(feature = "archive")]
+mod archive;
+#[cfg(feature = "git")]
+mod git;
@@ -31,7 +31,8 @@ fn test_help_msg() {
         .map(|l| l.to_string()) 
         .collect::<Vec<String>>();
 
-    assert_eq!(info, vec![
+    #[allow(unused_mut)]
+    let mut expected = vec![
         "lsrs - list directory contents",
         "Usage: lsrs [OPTIONS] [PATH]",
         "Arguments:",
@@ -43,11 +44,20 @@ fn test_help_msg() {
         "  -h, --human          print sizes in human-readable units",
         "  -r, --reverse        reverse order when sorting (-S, -t)",
         "  -S, --sort-size      sort by file size, largest first (specify -r for smallest first)",
-        "  -T, --show-time      show time (-T)",
+        "  -l, --long-listing   long listing (-l)",
         "  -t, --sort-mtime     sort by time modified, newest first (specify -r for oldest first)",
+        "  -v, --sort-version   sort by natural/version order (`file2` before `file10`); this is also the default when no other sort flag is given",
         "  -m, --stream-output  list files separated by `, `",
-    ]);
+        "  -1, --oneline        list one entry per line, instead of the default column grid",
+        "  -R, --recursive      list subdirectories recursively",
+        "  -F, --classify       append a character to each entry indicating its type (`/`=dir, `*`=executable, `@`=symlink, `|`=fifo, `=`=socket)",
+    ];
+    #[cfg(feature = "archive")]
+    expected.push("      --archive        list the entries inside a `.tar`/`.tar.gz`/`.zip` archive, rather than the archive file itself");
+    #[cfg(feature = "git")]
+    expected.push("      --git            show a per-file git status column in `-l` output");
 
+    assert_eq!(info, expected);
 }
 
 // #[test]